@@ -1,5 +1,5 @@
 use ruff_diagnostics::Applicability;
-use ruff_python_ast::{self as ast, ExceptHandler, Expr, ExprContext};
+use ruff_python_ast::{self as ast, ExceptHandler, Expr, ExprContext, Stmt};
 use ruff_text_size::{Ranged, TextRange};
 
 use ruff_macros::{ViolationMetadata, derive_message_formats};
@@ -12,10 +12,12 @@ use crate::importer::ImportRequest;
 use crate::{AlwaysFixableViolation, Edit, Fix};
 
 /// ## What it does
-/// Checks for uses of deprecated `zipfile.BadZipfile` that is aliased as `zipfile.BadZipFile`.
+/// Checks for uses of deprecated stdlib aliases, such as `zipfile.BadZipfile`,
+/// that have since been renamed.
 ///
 /// ## Why is this bad?
-/// `zipfile.BadZipfile` is deprecated since version 3.2 and may be removed in future versions
+/// Deprecated aliases are liable to be removed in future versions of Python,
+/// and projects should prefer the canonical name.
 ///
 /// ## Example
 /// ```python
@@ -37,53 +39,121 @@ use crate::{AlwaysFixableViolation, Edit, Fix};
 #[violation_metadata(preview_since = "NEXT_RUFF_VERSION")]
 pub(crate) struct BadZipFileAlias {
     name: Option<String>,
+    replacement: String,
 }
 
 impl AlwaysFixableViolation for BadZipFileAlias {
     #[derive_message_formats]
     fn message(&self) -> String {
-        "Replace aliased error with `BadZipFile`".to_string()
+        let BadZipFileAlias { replacement, .. } = self;
+        format!("Replace aliased error with `{replacement}`")
     }
 
     fn fix_title(&self) -> String {
-        let BadZipFileAlias { name } = self;
+        let BadZipFileAlias { name, replacement } = self;
         match name {
-            None => "Replace with `zipfile.BadZipFile`".to_string(),
-            Some(name) => format!("Replace `{name}` with `zipfile.BadZipFile`"),
+            None => format!("Replace with `{replacement}`"),
+            Some(name) => format!("Replace `{name}` with `{replacement}`"),
         }
     }
 }
 
-/// Return `true` if an [`Expr`] is an alias of `BadZipFile`.
-fn is_alias(expr: &Expr, semantic: &SemanticModel) -> bool {
+/// A single entry in the table of deprecated stdlib aliases, mapping a
+/// deprecated qualified name to the module and symbol that replaced it.
+struct DeprecatedAlias {
+    /// The qualified name segments of the deprecated reference, e.g.
+    /// `["zipfile", "BadZipfile"]`.
+    deprecated: &'static [&'static str],
+    /// The module that exports the replacement symbol.
+    replacement_module: &'static str,
+    /// The name of the replacement symbol within `replacement_module`.
+    replacement_symbol: &'static str,
+    /// The stdlib version `(major, minor)` in which the alias was deprecated.
+    #[expect(dead_code)]
+    since: (u8, u8),
+}
+
+impl DeprecatedAlias {
+    /// The dotted path to the replacement, e.g. `zipfile.BadZipFile`.
+    fn replacement(&self) -> String {
+        format!("{}.{}", self.replacement_module, self.replacement_symbol)
+    }
+}
+
+/// Return the attribute's `value` expression if `expr` is an attribute access (e.g.
+/// `zipfile.BadZipfile` or `zf.BadZipfile`) whose value resolves to the replacement's
+/// module, i.e. the module is already bound in scope (however it was imported) and the
+/// fix can rewrite the attribute in place instead of adding a new import.
+///
+/// The returned expression is the *actual* bound reference (e.g. `zf` for
+/// `import zipfile as zf`) and must be used verbatim in the replacement text, since
+/// `resolve_qualified_name` normalizes aliased imports back to the real module name.
+fn imported_module_value<'a>(
+    expr: &'a Expr,
+    alias: &DeprecatedAlias,
+    semantic: &SemanticModel,
+) -> Option<&'a Expr> {
+    let Expr::Attribute(attribute) = expr else {
+        return None;
+    };
     semantic
-        .resolve_qualified_name(expr)
-        .is_some_and(|qualified_name| {
-            matches!(qualified_name.segments(), ["zipfile", "BadZipfile"])
-        })
+        .resolve_qualified_name(&attribute.value)
+        .is_some_and(|qualified_name| qualified_name.segments() == [alias.replacement_module])
+        .then_some(&attribute.value)
+}
+
+/// The table of known deprecated stdlib aliases handled by this rule.
+static DEPRECATED_ALIASES: &[DeprecatedAlias] = &[DeprecatedAlias {
+    deprecated: &["zipfile", "BadZipfile"],
+    replacement_module: "zipfile",
+    replacement_symbol: "BadZipFile",
+    since: (3, 2),
+}];
+
+/// Return the [`DeprecatedAlias`] entry matching `expr`, if any.
+fn resolve_alias(expr: &Expr, semantic: &SemanticModel) -> Option<&'static DeprecatedAlias> {
+    let qualified_name = semantic.resolve_qualified_name(expr)?;
+    DEPRECATED_ALIASES
+        .iter()
+        .find(|alias| qualified_name.segments() == alias.deprecated)
 }
 
 /// Create a [`Diagnostic`] for a single target, like an [`Expr::Name`].
-fn atom_diagnostic(checker: &Checker, target: &Expr) {
+fn atom_diagnostic(checker: &Checker, target: &Expr, alias: &DeprecatedAlias) {
     let mut diagnostic = checker.report_diagnostic(
         BadZipFileAlias {
             name: UnqualifiedName::from_expr(target).map(|name| name.to_string()),
+            replacement: alias.replacement(),
         },
         target.range(),
     );
     diagnostic.try_set_fix(|| {
-        let (import_edit, binding) = checker.importer().get_or_import_symbol(
-            &ImportRequest::import_from("zipfile", "BadZipFile"),
-            target.start(),
-            checker.semantic(),
-        )?;
-
         let applicability = if checker.comment_ranges().intersects(target.range()) {
             Applicability::Unsafe
         } else {
             Applicability::Safe
         };
 
+        // If the module is already imported (however it's bound), rewrite the
+        // attribute in place rather than adding a redundant import.
+        if let Some(value) = imported_module_value(target, alias, checker.semantic()) {
+            let replacement = format!(
+                "{}.{}",
+                checker.locator().slice(value.range()),
+                alias.replacement_symbol
+            );
+            return Ok(Fix::applicable_edit(
+                Edit::range_replacement(replacement, target.range()),
+                applicability,
+            ));
+        }
+
+        let (import_edit, binding) = checker.importer().get_or_import_symbol(
+            &ImportRequest::import_from(alias.replacement_module, alias.replacement_symbol),
+            target.start(),
+            checker.semantic(),
+        )?;
+
         Ok(Fix::applicable_edits(
             Edit::range_replacement(binding, target.range()),
             [import_edit],
@@ -93,8 +163,19 @@ fn atom_diagnostic(checker: &Checker, target: &Expr) {
 }
 
 /// Create a [`Diagnostic`] for a tuple of expressions.
-fn tuple_diagnostic(checker: &Checker, tuple: &ast::ExprTuple, aliases: &[&Expr]) {
-    let mut diagnostic = checker.report_diagnostic(BadZipFileAlias { name: None }, tuple.range());
+fn tuple_diagnostic(
+    checker: &Checker,
+    tuple: &ast::ExprTuple,
+    aliases: &[&Expr],
+    alias: &DeprecatedAlias,
+) {
+    let mut diagnostic = checker.report_diagnostic(
+        BadZipFileAlias {
+            name: None,
+            replacement: alias.replacement(),
+        },
+        tuple.range(),
+    );
     let semantic = checker.semantic();
 
     let applicability = if checker.comment_ranges().intersects(tuple.range()) {
@@ -104,13 +185,30 @@ fn tuple_diagnostic(checker: &Checker, tuple: &ast::ExprTuple, aliases: &[&Expr]
     };
 
     diagnostic.try_set_fix(|| {
-        let (import_edit, binding) = checker.importer().get_or_import_symbol(
-            &ImportRequest::import_from("zipfile", "BadZipFile"),
-            tuple.start(),
-            checker.semantic(),
-        )?;
+        // If the module is already imported (however it's bound), reuse the attribute
+        // form (e.g. `zf.BadZipFile`) instead of adding a redundant import.
+        let (import_edit, binding) = if let Some(value) = aliases
+            .first()
+            .and_then(|alias_expr| imported_module_value(alias_expr, alias, semantic))
+        {
+            (
+                None,
+                format!(
+                    "{}.{}",
+                    checker.locator().slice(value.range()),
+                    alias.replacement_symbol
+                ),
+            )
+        } else {
+            let (import_edit, binding) = checker.importer().get_or_import_symbol(
+                &ImportRequest::import_from(alias.replacement_module, alias.replacement_symbol),
+                tuple.start(),
+                checker.semantic(),
+            )?;
+            (Some(import_edit), binding)
+        };
 
-        // Filter out any `BadZipFile` aliases.
+        // Filter out any aliases that already match this entry.
         let mut remaining: Vec<Expr> = tuple
             .iter()
             .filter_map(|element| {
@@ -122,12 +220,14 @@ fn tuple_diagnostic(checker: &Checker, tuple: &ast::ExprTuple, aliases: &[&Expr]
             })
             .collect();
 
-        // If `BadZipFile` itself isn't already in the tuple, add it.
+        // If the replacement itself isn't already in the tuple, add it.
         // Use the binding name from get_or_import_symbol, which handles existing imports correctly.
         if tuple.iter().all(|element| {
             semantic
                 .resolve_qualified_name(element)
-                .map(|qn| qn.segments() != ["zipfile", "BadZipFile"])
+                .map(|qn| {
+                    qn.segments() != [alias.replacement_module, alias.replacement_symbol]
+                })
                 .unwrap_or(true)
         }) {
             let node = ast::ExprName {
@@ -157,12 +257,97 @@ fn tuple_diagnostic(checker: &Checker, tuple: &ast::ExprTuple, aliases: &[&Expr]
                 pad(content, tuple.range(), checker.locator()),
                 tuple.range(),
             ),
-            [import_edit],
+            import_edit,
             applicability,
         ))
     });
 }
 
+/// Return `true` if `expr` resolves to the builtin `AttributeError` or `ImportError`,
+/// whether referenced directly or as an element of a tuple of exception types.
+fn is_compat_exception(expr: &Expr, semantic: &SemanticModel) -> bool {
+    match expr {
+        Expr::Tuple(tuple) => tuple
+            .iter()
+            .any(|element| is_compat_exception(element, semantic)),
+        _ => semantic
+            .resolve_qualified_name(expr)
+            .is_some_and(|qualified_name| {
+                matches!(
+                    qualified_name.segments(),
+                    ["AttributeError"] | ["builtins", "AttributeError"]
+                        | ["ImportError"]
+                        | ["builtins", "ImportError"]
+                )
+            }),
+    }
+}
+
+/// Return `true` if the statement currently being visited lives within any branch
+/// (`try`, `except`, `else`, or `finally`) of a `try` statement that has a handler for
+/// `AttributeError` or `ImportError`, i.e. it looks like an intentional version-
+/// compatibility shim. This deliberately covers the `except` fallback branch too: the
+/// motivating pattern is `try: exc = New; except AttributeError: exc = Old`, where the
+/// deprecated reference lives in the handler, not the `try` body, and rewriting it
+/// would defeat the shim just the same.
+///
+/// The walk doesn't stop at the innermost enclosing `try`: a `try` without a compat
+/// handler (e.g. `except ValueError`) doesn't itself shield its branches, but if it's
+/// nested inside an outer `try` that *does* catch `AttributeError`/`ImportError`, the
+/// reference is still inside that outer shim and should be suppressed. So we keep
+/// walking outward past non-compat `try`s, and only give up once we leave every branch
+/// of a `try` statement (into some other kind of ancestor) or run out of ancestors.
+fn is_within_compat_try_body(checker: &Checker) -> bool {
+    let semantic = checker.semantic();
+    let mut statements = semantic.current_statements();
+    let Some(mut previous) = statements.next() else {
+        return false;
+    };
+    for stmt in statements {
+        let Stmt::Try(ast::StmtTry {
+            body,
+            handlers,
+            orelse,
+            finalbody,
+            ..
+        }) = stmt
+        else {
+            previous = stmt;
+            continue;
+        };
+
+        let in_try_statement = body
+            .iter()
+            .chain(orelse)
+            .chain(finalbody)
+            .chain(handlers.iter().flat_map(|handler| {
+                let ExceptHandler::ExceptHandler(ast::ExceptHandlerExceptHandler {
+                    body, ..
+                }) = handler;
+                body.iter()
+            }))
+            .any(|stmt| stmt.range() == previous.range());
+
+        if !in_try_statement {
+            return false;
+        }
+
+        if handlers.iter().any(|handler| {
+            let ExceptHandler::ExceptHandler(ast::ExceptHandlerExceptHandler {
+                type_, ..
+            }) = handler;
+            type_
+                .as_ref()
+                .is_some_and(|type_| is_compat_exception(type_, semantic))
+        }) {
+            return true;
+        }
+
+        previous = stmt;
+    }
+    false
+}
+
 /// UP051
 pub(crate) fn badzipfile_alias_handlers(checker: &Checker, handlers: &[ExceptHandler]) {
     for handler in handlers {
@@ -172,20 +357,27 @@ pub(crate) fn badzipfile_alias_handlers(checker: &Checker, handlers: &[ExceptHan
         };
         match expr.as_ref() {
             Expr::Name(_) | Expr::Attribute(_) => {
-                if is_alias(expr, checker.semantic()) {
-                    atom_diagnostic(checker, expr);
+                if let Some(alias) = resolve_alias(expr, checker.semantic()) {
+                    if !is_within_compat_try_body(checker) {
+                        atom_diagnostic(checker, expr, alias);
+                    }
                 }
             }
             Expr::Tuple(tuple) => {
-                // List of aliases to replace with `BadZipFile`.
+                // Collect every element that matches a deprecated alias. Elements are
+                // not grouped by entry: the fix uses the last matched entry's replacement.
                 let mut aliases: Vec<&Expr> = vec![];
+                let mut matched = None;
                 for element in tuple {
-                    if is_alias(element, checker.semantic()) {
+                    if let Some(alias) = resolve_alias(element, checker.semantic()) {
                         aliases.push(element);
+                        matched = Some(alias);
                     }
                 }
-                if !aliases.is_empty() {
-                    tuple_diagnostic(checker, tuple, &aliases);
+                if let Some(alias) = matched {
+                    if !is_within_compat_try_body(checker) {
+                        tuple_diagnostic(checker, tuple, &aliases, alias);
+                    }
                 }
             }
             _ => {}
@@ -195,16 +387,158 @@ pub(crate) fn badzipfile_alias_handlers(checker: &Checker, handlers: &[ExceptHan
 
 /// UP051
 pub(crate) fn badzipfile_alias_call(checker: &Checker, func: &Expr) {
-    if is_alias(func, checker.semantic()) {
-        atom_diagnostic(checker, func);
+    if let Some(alias) = resolve_alias(func, checker.semantic()) {
+        if !is_within_compat_try_body(checker) {
+            atom_diagnostic(checker, func, alias);
+        }
     }
 }
 
 /// UP051
 pub(crate) fn badzipfile_alias_raise(checker: &Checker, expr: &Expr) {
     if matches!(expr, Expr::Name(_) | Expr::Attribute(_)) {
-        if is_alias(expr, checker.semantic()) {
-            atom_diagnostic(checker, expr);
+        if let Some(alias) = resolve_alias(expr, checker.semantic()) {
+            if !is_within_compat_try_body(checker) {
+                atom_diagnostic(checker, expr, alias);
+            }
+        }
+    }
+}
+
+/// Return `true` if `expr` is, or is an element of, the `type_` of an `except` handler
+/// belonging to the `try` statement innermost-enclosing `expr`. Either way, the
+/// reference is already reported by [`badzipfile_alias_handlers`]: as a whole via
+/// [`atom_diagnostic`] if it's a single type, or as part of the tuple via
+/// [`tuple_diagnostic`] if it's one of several.
+fn is_except_handler_type(expr: &Expr, semantic: &SemanticModel) -> bool {
+    let Some(Stmt::Try(ast::StmtTry { handlers, .. })) = semantic.current_statements().next()
+    else {
+        return false;
+    };
+    handlers.iter().any(|handler| {
+        let ExceptHandler::ExceptHandler(ast::ExceptHandlerExceptHandler { type_, .. }) = handler;
+        let Some(type_) = type_.as_ref() else {
+            return false;
+        };
+        if type_.range() == expr.range() {
+            return true;
+        }
+        matches!(type_.as_ref(), Expr::Tuple(tuple) if tuple.iter().any(|element| element.range() == expr.range()))
+    })
+}
+
+/// Return `true` if `expr` is the `exc` of the innermost-enclosing `raise` statement.
+/// Such references are already reported by [`badzipfile_alias_raise`].
+fn is_raise_target(expr: &Expr, semantic: &SemanticModel) -> bool {
+    matches!(
+        semantic.current_statement(),
+        Stmt::Raise(ast::StmtRaise { exc: Some(exc), .. }) if exc.range() == expr.range()
+    )
+}
+
+/// Return `true` if `expr` is, or is an element of, the callee of a call, or the
+/// relevant argument to an `isinstance`/`issubclass` call. Either way, the reference is
+/// already reported by [`badzipfile_alias_call`] or [`badzipfile_alias_isinstance`]: as
+/// a whole via [`atom_diagnostic`] if it's a single type, or as part of the tuple via
+/// [`tuple_diagnostic`] if it's one of several (e.g. `isinstance(err, (Deprecated,
+/// ValueError))`).
+fn is_call_or_isinstance_argument(expr: &Expr, semantic: &SemanticModel) -> bool {
+    let call = match semantic.current_expression_parent() {
+        Some(Expr::Call(call)) => call,
+        Some(Expr::Tuple(_)) => match semantic.current_expression_grandparent() {
+            Some(Expr::Call(call)) => call,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    if call.func.range() == expr.range() {
+        return true;
+    }
+    if !(semantic.match_builtin_expr(&call.func, "isinstance")
+        || semantic.match_builtin_expr(&call.func, "issubclass"))
+    {
+        return false;
+    }
+    let Some(second_argument) = call.arguments.args.get(1) else {
+        return false;
+    };
+    if second_argument.range() == expr.range() {
+        return true;
+    }
+    matches!(second_argument, Expr::Tuple(tuple) if tuple.iter().any(|element| element.range() == expr.range()))
+}
+
+/// UP051
+///
+/// A generic fallback that catches any `Name`/`Attribute` reference resolving to a
+/// deprecated alias, regardless of where it appears (e.g. a bare assignment like
+/// `E = zipfile.BadZipfile`). Dispatches on the concrete [`Expr`] node kind, mirroring
+/// how `pandas_vet` separates its attribute/call/subscript handling, and defers to the
+/// more specific handlers above for positions they already cover.
+pub(crate) fn badzipfile_alias_expr(checker: &Checker, expr: &Expr) {
+    if !matches!(expr, Expr::Name(_) | Expr::Attribute(_)) {
+        return;
+    }
+
+    let semantic = checker.semantic();
+    let Some(alias) = resolve_alias(expr, semantic) else {
+        return;
+    };
+
+    if is_except_handler_type(expr, semantic)
+        || is_raise_target(expr, semantic)
+        || is_call_or_isinstance_argument(expr, semantic)
+    {
+        return;
+    }
+
+    if !is_within_compat_try_body(checker) {
+        atom_diagnostic(checker, expr, alias);
+    }
+}
+
+/// UP051
+///
+/// Checks the second argument of an `isinstance`/`issubclass` call (e.g.
+/// `isinstance(err, zipfile.BadZipfile)`), handling both a single alias and a tuple of
+/// alias entries, and reuses [`atom_diagnostic`]/[`tuple_diagnostic`] exactly as the
+/// existing `except`-handler tuple path does.
+pub(crate) fn badzipfile_alias_isinstance(checker: &Checker, call: &ast::ExprCall) {
+    let semantic = checker.semantic();
+
+    if !(semantic.match_builtin_expr(&call.func, "isinstance")
+        || semantic.match_builtin_expr(&call.func, "issubclass"))
+    {
+        return;
+    }
+
+    let Some(second_argument) = call.arguments.args.get(1) else {
+        return;
+    };
+
+    if is_within_compat_try_body(checker) {
+        return;
+    }
+
+    match second_argument {
+        Expr::Tuple(tuple) => {
+            let mut aliases: Vec<&Expr> = vec![];
+            let mut matched = None;
+            for element in tuple {
+                if let Some(alias) = resolve_alias(element, semantic) {
+                    aliases.push(element);
+                    matched = Some(alias);
+                }
+            }
+            if let Some(alias) = matched {
+                tuple_diagnostic(checker, tuple, &aliases, alias);
+            }
+        }
+        Expr::Name(_) | Expr::Attribute(_) => {
+            if let Some(alias) = resolve_alias(second_argument, semantic) {
+                atom_diagnostic(checker, second_argument, alias);
+            }
         }
+        _ => {}
     }
 }